@@ -0,0 +1,39 @@
+//! Byte-level access to a compressed V3C sample stream, and the V3C unit
+//! parser built on top of it (see `bitstream::reader`).
+
+pub mod reader;
+
+/// A fully-buffered compressed bitstream, positioned for sequential reads.
+///
+/// `File`/`Memory` sources load everything up front via `from_file`/
+/// `from_bytes`; `reader::SampleStreamV3CUnit::from_reader` takes the
+/// incremental path instead and never constructs one of these.
+#[derive(Debug, Clone, Default)]
+pub struct Bitstream {
+    data: Vec<u8>,
+    position: usize,
+}
+
+impl Bitstream {
+    pub fn from_bytes(data: Vec<u8>) -> Self {
+        Self { data, position: 0 }
+    }
+
+    pub fn from_file(path: &std::path::Path) -> Self {
+        let data = std::fs::read(path)
+            .unwrap_or_else(|err| panic!("failed to read bitstream {}: {err}", path.display()));
+        Self::from_bytes(data)
+    }
+
+    pub fn remaining(&self) -> &[u8] {
+        &self.data[self.position..]
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+}
@@ -0,0 +1,18 @@
+//! The decoded point cloud representation handed back to callers.
+
+/// A single `(x, y, z)` triple, generic over the component type so the same
+/// shape is reused for both integer geometry positions and `u8` colors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Point3D<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+/// A decoded point cloud frame.
+#[derive(Debug, Clone, Default)]
+pub struct PointSet3 {
+    pub positions: Vec<Point3D<i32>>,
+    pub colors: Vec<Point3D<u8>>,
+    pub with_colors: bool,
+}
@@ -0,0 +1,42 @@
+//! Decode state threaded through one V3C unit's processing, carried forward
+//! across units by `Decoder::start` so atlas/video data units that don't
+//! repeat the VPS can still be decoded against the right one.
+
+/// The most recently parsed V3C Parameter Set.
+#[derive(Debug, Clone, Default)]
+pub struct ActiveVps {
+    pub vps_id: u8,
+    pub atlas_count: u8,
+}
+
+/// Per-atlas state accumulated from AD/OVD/GVD/AVD units, and what the
+/// bitstream signals those units actually carry (consumed by
+/// `ReconstructionConfig::validate`).
+#[derive(Debug, Clone, Default)]
+pub struct AtlasContext {
+    pub occupancy_nalus: Vec<u8>,
+    pub geometry_nalus: Vec<u8>,
+    pub attribute_nalus: Vec<u8>,
+    pub eom_patches_present: bool,
+    pub raw_patches_present: bool,
+    pub occupancy_map_present: bool,
+    pub attributes_present: bool,
+    /// Frame geometry signaled for this atlas's video substreams, needed to
+    /// interpret a raw planar `DecodedFrame` buffer.
+    pub frame_width: usize,
+    pub frame_height: usize,
+    pub bit_depth: u8,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Context {
+    /// The VPS in effect for this unit, threaded forward by `Decoder::start`
+    /// (see the module doc above). `decoder::Decoder::decode` doesn't read
+    /// this yet — there's only ever one atlas's worth of bitstream framing
+    /// parsed per unit in this snapshot's `bitstream::reader`, so
+    /// `vps_id`/`atlas_count` don't change what gets decoded — but it's
+    /// threaded correctly so a per-atlas-id-aware parser can start reading
+    /// it without also having to fix how it's carried across units.
+    pub active_vps: Option<ActiveVps>,
+    pub atlas_contexts: Vec<AtlasContext>,
+}
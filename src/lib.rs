@@ -2,25 +2,38 @@ mod bitstream;
 pub mod codec;
 mod common;
 mod decoder;
+pub mod reconstruction;
+pub mod video_decoder;
 pub mod writer;
 
 use bitstream::Bitstream;
 use codec::PointSet3;
 use common::context::Context;
 use crossbeam_channel as chan;
+use reconstruction::ReconstructionConfig;
+use std::collections::BTreeMap;
+use std::fmt;
 use std::sync::mpsc::{channel, Receiver};
+use std::sync::{Arc, Condvar, Mutex};
 use std::path::PathBuf;
 use std::thread;
 use std::fs::File;
-use std::io::Write;
+use std::io::{Read, Write};
 
+use numpy::ndarray::Array2;
+use numpy::{IntoPyArray, PyArray2};
 use pyo3::prelude::*;
-use pyo3::types::{PyBytes, PyDict, PyList, PyTuple};
+use pyo3::types::{PyBytes, PyDict};
 
-#[derive(Debug, Clone)]
 pub enum BitstreamSource {
     File(PathBuf),
     Memory(Vec<u8>),
+    /// A live/networked source (e.g. a socket, or an RTP-style depayloader
+    /// reassembling V3C units from packets) that doesn't have the whole
+    /// compressed stream available up front. `Decoder::start` parses and
+    /// decodes each V3C unit as soon as its sample-stream length prefix is
+    /// satisfied, rather than waiting for EOF like `File`/`Memory` do.
+    Stream(Box<dyn Read + Send>),
 }
 
 impl Default for BitstreamSource {
@@ -29,17 +42,62 @@ impl Default for BitstreamSource {
     }
 }
 
+impl fmt::Debug for BitstreamSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BitstreamSource::File(path) => f.debug_tuple("File").field(path).finish(),
+            BitstreamSource::Memory(data) => f.debug_tuple("Memory").field(data).finish(),
+            BitstreamSource::Stream(_) => f.debug_tuple("Stream").field(&"<reader>").finish(),
+        }
+    }
+}
+
+// NOTE (28Jul26): deliberately *not* `Clone`. A `Stream` owns a
+// `Box<dyn Read + Send>`, which can't be read twice, so there's no
+// meaningful way to clone one; rather than have `.clone()` silently hand
+// back an empty `Memory` source (a decoder that produces no frames with no
+// indication why), `Params`/`BitstreamSource` just don't implement `Clone`.
+// `Decoder::start` takes `self.params` by value via `Option::take` instead
+// of cloning it.
+
+/// Shared state behind the worker pool's reorder buffer, guarded by a
+/// `Mutex`/`Condvar` pair (see `Decoder::start`).
+///
+/// Backpressure is applied at *dispatch*, not at this insert: the
+/// dispatcher only sends a unit once `in_flight < max_in_flight`, and a
+/// unit stays "in flight" (whether still queued, being worked on, or
+/// sitting in `pending` waiting for the emitter) until the emitter removes
+/// it at `next_index` and releases its slot. That's what lets workers
+/// always insert their result here unconditionally — if insertion itself
+/// blocked on `pending`'s size instead, the worker holding the *next*
+/// index the emitter needs could be stuck waiting for space that only the
+/// emitter (blocked on that same worker) can free, deadlocking the whole
+/// pipeline.
+#[derive(Default)]
+struct ReorderState {
+    pending: BTreeMap<usize, Vec<PointSet3>>,
+    workers_done: bool,
+    /// Units dispatched but not yet removed from `pending` by the emitter.
+    in_flight: usize,
+    /// Set once the emitter can no longer forward anything (the `recv_frame`
+    /// receiver was dropped), so the dispatcher and any worker/emitter
+    /// waiting on this condvar stop and exit instead of doing work nobody
+    /// will ever consume.
+    aborted: bool,
+}
 
 /// The library's decoder
 pub struct Decoder {
-    params: Params,
+    // will be None once the decoder is started: `start()` takes it by value
+    // rather than cloning, since `Params` isn't `Clone` (see `BitstreamSource`).
+    params: Option<Params>,
     // will be None once the decoder is started.
     tx: Option<chan::Sender<PointSet3>>,
     rx: chan::Receiver<PointSet3>,
 }
 
 /// Params to pass in to the library's decoder
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default)]
 pub struct Params {
     // NOTE: we don't need start_frame and reconstructed_data_path while decoding
     // pub start_frame: usize,
@@ -47,6 +105,21 @@ pub struct Params {
     pub source: BitstreamSource,
     pub compressed_stream_path: PathBuf,
     pub video_decoder_path: Option<PathBuf>,
+    // NOTE (28Jul26): sizes the bounded channel `recv_frame()`/
+    // `try_recv_frame()` read from. `None` (or `Some(n)` with `n < 1`) keeps
+    // the previous depth-1 behavior, where the decode thread blocks until
+    // each frame is consumed; a deeper buffer lets decode run ahead of a
+    // slow consumer.
+    pub frame_buffer_depth: Option<usize>,
+    // NOTE (28Jul26): selects a backend by `VideoDecoderBackend::name()`
+    // from the `VideoDecoderRegistry` the decoder builds at start (e.g.
+    // `"vaapi"`); `None`, or a name with no backend that supports the
+    // requested codec/profile, falls back to `"software"`.
+    pub video_decoder_backend: Option<String>,
+    // NOTE (28Jul26): `None` means "pick a worker count from
+    // `std::thread::available_parallelism()`"; `Some(1)` forces the original
+    // single-thread decode path.
+    pub num_threads: Option<usize>,
     // NOTE (2Jan23): always true
     // pub is_bytestream_video_coder: bool,
     pub keep_intermediate_files: bool,
@@ -55,17 +128,13 @@ pub struct Params {
     pub color_space_conversion_path: Option<PathBuf>,
     pub inverse_color_space_conversion_config: Option<PathBuf>,
 
-    // reconstruction options
-    // NOTE (9Dec22): all set to default (false) for now since we are only supporting Rec0
-    pixel_deinterleaving_type: bool,
-    point_local_reconstruction_type: bool,
-    reconstruction_eom_type: bool,
-    _duplicated_point_removal_type: bool,
-    reconstruct_raw_type: bool,
-    apply_geo_smoothing_type: bool,
-    apply_attr_smoothing_type: bool,
-    attr_transfer_filter_type: bool,
-    apply_occupancy_synthesis_type: bool,
+    /// Which post-decode reconstruction passes to run. Defaults to
+    /// `ReconstructionConfig::rec0()` (everything off, matching the
+    /// decoder's previous hardcoded behavior); build one with the
+    /// `ReconstructionConfig` builder, or load one from a declarative file
+    /// with `ReconstructionConfig::from_file`, and set it with
+    /// `Params::reconstruction`.
+    pub reconstruction: ReconstructionConfig,
 }
 
 impl Params {
@@ -85,13 +154,26 @@ impl Params {
     //     self.video_decoder_path = Some(video_decoder_path);
     //     self
     // }
+
+    /// Sets which reconstruction passes the decoder runs after decoding
+    /// each frame's video substreams.
+    pub fn reconstruction(mut self, reconstruction: ReconstructionConfig) -> Self {
+        self.reconstruction = reconstruction;
+        self
+    }
 }
 
 impl Decoder {
     pub fn new(params: Params) -> Self {
-        let (tx, rx) = chan::bounded(1);
+        // NOTE (28Jul26): a depth of 1 (the previous hardcoded value)
+        // serializes decode and consumption, since the decode thread blocks
+        // on `tx.send()` until the consumer calls `recv_frame()`/
+        // `try_recv_frame()`. `Params::frame_buffer_depth` lets the decode
+        // thread run that many frames ahead of a slow consumer instead.
+        let depth = params.frame_buffer_depth.unwrap_or(1).max(1);
+        let (tx, rx) = chan::bounded(depth);
         Self {
-            params,
+            params: Some(params),
             tx: Some(tx),
             rx,
         }
@@ -123,48 +205,251 @@ impl Decoder {
     /// }
     /// ```
     pub fn start(&mut self) {
-        let bitstream = match &self.params.source {
-            BitstreamSource::File(path) => Bitstream::from_file(path),
-            BitstreamSource::Memory(data) => Bitstream::from_bytes(data.clone()),
-        };
+        // Taken by value rather than cloned: `Params` isn't `Clone` (a
+        // `BitstreamSource::Stream` owns a `Box<dyn Read + Send>`, which
+        // can't be read twice, so cloning it could only ever be lossy).
+        let mut params = self
+            .params
+            .take()
+            .expect("library decoder can only be started once");
+        let source = std::mem::take(&mut params.source);
         // let mut bitstream_stat = bitstream::Stat::new();
         // TODO[checks] bitstream.computeMD5()
         // TODO[stat] (9Dec22): Do everything related to bitstream_stat
         // bitstream_stat.header = bitstream.size()
-        let (mut ssvu, _header_size) =
-            bitstream::reader::SampleStreamV3CUnit::from_bitstream(&bitstream);
-        // TODO[stat] bitstream_stat.incr_header(header_size);
+        let mut ssvu = match source {
+            BitstreamSource::File(path) => {
+                let bitstream = Bitstream::from_file(&path);
+                let (ssvu, _header_size) =
+                    bitstream::reader::SampleStreamV3CUnit::from_bitstream(&bitstream);
+                // TODO[stat] bitstream_stat.incr_header(header_size);
+                ssvu
+            }
+            BitstreamSource::Memory(data) => {
+                let bitstream = Bitstream::from_bytes(data);
+                let (ssvu, _header_size) =
+                    bitstream::reader::SampleStreamV3CUnit::from_bitstream(&bitstream);
+                // TODO[stat] bitstream_stat.incr_header(header_size);
+                ssvu
+            }
+            BitstreamSource::Stream(reader) => {
+                // Incremental path for live/networked input: unlike
+                // `File`/`Memory` above, there's no whole compressed stream
+                // to parse a header from up front. `SampleStreamV3CUnit`
+                // buffers partial reads at unit boundaries and parses each
+                // unit's sample-stream length prefix as enough bytes
+                // arrive, decoding it immediately rather than waiting for
+                // EOF; it signals graceful end-of-stream once `reader`
+                // closes (`get_v3c_unit_count()` settles at 0).
+                bitstream::reader::SampleStreamV3CUnit::from_reader(reader)
+            }
+        };
 
-        let decoder = decoder::Decoder::new(self.params.clone());
         let tx = self
             .tx
             .take()
             .expect("library decoder can only be started once");
 
+        let num_threads = params.num_threads.unwrap_or_else(|| {
+            thread::available_parallelism()
+                .map(|n| n.get())
+                .unwrap_or(1)
+        });
+
+        // Moves the (now source-less) `params` into `decoder::Decoder`,
+        // which reads `reconstruction`/`video_decoder_backend` from it.
+        let decoder = Arc::new(decoder::Decoder::new(params));
+
+        if num_threads <= 1 {
+            thread::spawn(move || {
+                // Carried forward from whichever unit last set it, so an
+                // AD/OVD/GVD/AVD unit that doesn't repeat the VPS is still
+                // decoded against the right one (see the worker-pool
+                // dispatch loop below for the same pattern).
+                let mut active_vps = None;
+                while ssvu.get_v3c_unit_count() > 0 {
+                    // DIFF: This is different (I think) from the reference implementation.
+                    let mut context = Context {
+                        active_vps: active_vps.clone(),
+                        ..Context::default()
+                    };
+                    // TODO[stat] context.set_bitstream_stat(&bitstream_stat);
+                    ssvu.decode(&mut context);
+                    // TODO[checks]: context.check_profile()
+                    active_vps = context.active_vps.clone();
+
+                    if let Err(_) = decoder.decode(&mut context, tx.clone()) {
+                        // receiver `rx` dropped, or the unit failed
+                        // validation/video decoding: stop decoding.
+                        break;
+                    }
+
+                    // SKIP: a bunch of if clauses on metrics.
+                }
+
+                drop(tx);
+            });
+            return;
+        }
+
+        // Worker-pool path: `ssvu.decode()` (parsing + VPS bookkeeping) stays on a
+        // single dispatch thread since each unit's `active_vps` depends on the one
+        // immediately preceding it, but the much heavier `decoder.decode()` pass
+        // (video substream decode + reconstruction) is fanned out over
+        // `num_threads` workers. Workers finish out of order, so each dispatched
+        // unit carries a monotonically increasing `index`, and a reorder buffer
+        // (mutex + condvar guarding a `BTreeMap<index, Vec<PointSet3>>`) re-serializes
+        // results before they're forwarded to `tx`, so `recv_frame()` still sees
+        // frames in presentation order.
         thread::spawn(move || {
-            // IDEA (9Dec22): We can parallelize iterations of this loop, since the data is self-contained.
-            // i.e. AD, OVD, GVD, AVD are independent only of the VPS that immediately precedes it.
-            // In the reference implementation, after running `ssvu.decode(...)`, the decoder is run, which kinda implies that there is some potential for parallelism here.
-            // Check how `context.active_vps` is updated.
+            let (work_tx, work_rx) = chan::bounded::<(usize, Context)>(num_threads * 2);
+            let reorder = Arc::new((Mutex::new(ReorderState::default()), Condvar::new()));
+            // Caps how many units may be dispatched-but-not-yet-emitted at
+            // once (queued, being worked on, or sitting in `pending`
+            // waiting for the emitter). The *dispatcher* blocks on this
+            // cap, not the workers' `pending` insert — see `ReorderState`
+            // for why gating the insert itself deadlocks. Sized off
+            // `num_threads` so there's enough slack for every worker to
+            // have a result in flight plus a little headroom, without
+            // unbounded growth while the emitter is stalled behind a slow
+            // consumer (`tx` full).
+            let max_in_flight = num_threads * 4;
+
+            let workers: Vec<_> = (0..num_threads)
+                .map(|_| {
+                    let work_rx = work_rx.clone();
+                    // `decoder::Decoder` is shared read-only across workers
+                    // as `Arc<decoder::Decoder>` (not `Arc<Mutex<..>>`):
+                    // `decode()` takes `&self`, holds no per-call mutable
+                    // state on `Decoder` itself, and each worker passes in
+                    // its own freshly cloned `Context`, so there's no
+                    // shared mutable decoder state for workers to contend
+                    // on. This requires `decoder::Decoder: Sync`.
+                    let decoder = Arc::clone(&decoder);
+                    let reorder = Arc::clone(&reorder);
+                    thread::spawn(move || {
+                        for (index, mut context) in work_rx.iter() {
+                            // Each work item owns its own `Context`, cloned with the
+                            // correct `active_vps` at dispatch time, so workers never
+                            // contend on shared mutable decoder state. A unit's decode
+                            // pass may emit more than one frame, so drain every frame
+                            // it produces before handing the batch to the reorder
+                            // buffer under its dispatch index.
+                            let (item_tx, item_rx) = chan::unbounded();
+                            let frames = match decoder.decode(&mut context, item_tx) {
+                                Ok(()) => item_rx.iter().collect(),
+                                Err(_) => Vec::new(),
+                            };
+                            // Always insert, uncontended by capacity: the
+                            // dispatcher already ensured this unit's slot
+                            // stays accounted for in `in_flight` until the
+                            // emitter consumes it below.
+                            let (lock, cvar) = &*reorder;
+                            let mut state = lock.lock().unwrap();
+                            state.pending.insert(index, frames);
+                            drop(state);
+                            cvar.notify_all();
+                        }
+                    })
+                })
+                .collect();
+
+            let emitter = {
+                let reorder = Arc::clone(&reorder);
+                thread::spawn(move || {
+                    let (lock, cvar) = &*reorder;
+                    let mut next_index = 0;
+                    loop {
+                        let mut state = lock.lock().unwrap();
+                        while !state.pending.contains_key(&next_index) && !state.workers_done {
+                            state = cvar.wait(state).unwrap();
+                        }
+                        match state.pending.remove(&next_index) {
+                            Some(frames) => {
+                                // Frees this unit's in-flight slot so the
+                                // dispatcher can admit another one.
+                                state.in_flight -= 1;
+                                drop(state);
+                                cvar.notify_all();
+                                let mut disconnected = false;
+                                for point_set in frames {
+                                    if tx.send(point_set).is_err() {
+                                        // receiver `rx` dropped, so we can stop decoding.
+                                        disconnected = true;
+                                        break;
+                                    }
+                                }
+                                if disconnected {
+                                    // Wakes the dispatcher (if it's blocked
+                                    // waiting for in-flight headroom) so it
+                                    // notices `aborted` and stops dispatching
+                                    // more units nobody will ever receive.
+                                    let mut state = lock.lock().unwrap();
+                                    state.aborted = true;
+                                    cvar.notify_all();
+                                    break;
+                                }
+                                next_index += 1;
+                            }
+                            // Workers are done and there's nothing left to reorder.
+                            None => break,
+                        }
+                    }
+                    drop(tx);
+                })
+            };
+
+            let mut index = 0;
+            // Carried forward from whichever unit last set it and cloned
+            // into each work item below, so an AD/OVD/GVD/AVD unit that
+            // doesn't repeat the VPS is still dispatched with the right
+            // one, even though it's decoded out of order from the unit
+            // that set it.
+            let mut active_vps = None;
             while ssvu.get_v3c_unit_count() > 0 {
                 // DIFF: This is different (I think) from the reference implementation.
-                let mut context = Context::default();
-                // TODO[stat] context.set_bitstream_stat(&bitstream_stat);
+                let mut context = Context {
+                    active_vps: active_vps.clone(),
+                    ..Context::default()
+                };
                 ssvu.decode(&mut context);
-                // TODO[checks]: context.check_profile()
-
-                // context.atlas_contexts[i].allocate_video_frames(&mut context);
-                // context.atlas_index = atl_id as u8;
+                active_vps = context.active_vps.clone();
+
+                // Blocks until a slot opens up rather than gating the
+                // workers' `pending` insert (see `ReorderState`).
+                {
+                    let (lock, cvar) = &*reorder;
+                    let mut state = lock.lock().unwrap();
+                    while state.in_flight >= max_in_flight && !state.aborted {
+                        state = cvar.wait(state).unwrap();
+                    }
+                    if state.aborted {
+                        break;
+                    }
+                    state.in_flight += 1;
+                }
 
-                if let Err(_) = decoder.decode(&mut context, tx.clone()) {
-                    // receiver `rx` dropped, so we can stop decoding.
+                if work_tx.send((index, context)).is_err() {
+                    let (lock, cvar) = &*reorder;
+                    let mut state = lock.lock().unwrap();
+                    state.in_flight -= 1;
+                    cvar.notify_all();
                     break;
                 }
+                index += 1;
+            }
+            drop(work_tx);
 
-                // SKIP: a bunch of if clauses on metrics.
+            for worker in workers {
+                let _ = worker.join();
             }
 
-            drop(tx);
+            {
+                let (lock, cvar) = &*reorder;
+                lock.lock().unwrap().workers_done = true;
+                cvar.notify_all();
+            }
+            let _ = emitter.join();
         });
     }
 
@@ -174,6 +459,39 @@ impl Decoder {
     pub fn recv_frame(&self) -> Option<PointSet3> {
         self.rx.recv().ok()
     }
+
+    /// Reports whether the decode thread still has slack to accept more work,
+    /// i.e. the bounded output channel isn't currently full.
+    ///
+    /// This lets a caller driving an event loop decide whether it's worth
+    /// kicking the decoder again before polling for the next frame.
+    pub fn can_take_input(&self) -> bool {
+        !self.rx.is_full()
+    }
+
+    /// Non-blocking counterpart to `recv_frame()`.
+    ///
+    /// Never blocks: returns `TryRecvState::NotReady` immediately if no frame
+    /// is available yet instead of waiting, so callers can poll from an event
+    /// loop or a display tick rather than dedicating a thread to `recv_frame()`.
+    pub fn try_recv_frame(&self) -> TryRecvState {
+        match self.rx.try_recv() {
+            Ok(point_set) => TryRecvState::Frame(point_set),
+            Err(chan::TryRecvError::Empty) => TryRecvState::NotReady,
+            Err(chan::TryRecvError::Disconnected) => TryRecvState::Done,
+        }
+    }
+}
+
+/// Result of a non-blocking `Decoder::try_recv_frame()` poll.
+#[derive(Debug)]
+pub enum TryRecvState {
+    /// A decoded frame was ready.
+    Frame(PointSet3),
+    /// No frame is ready yet; the decode thread is still working on it.
+    NotReady,
+    /// The decode thread has finished and no more frames will ever arrive.
+    Done,
 }
 
 impl Iterator for Decoder {
@@ -185,6 +503,47 @@ impl Iterator for Decoder {
 }
 
 
+/// Returned by `PyTMC2Decoder::try_next_frame()` when polled before the next
+/// frame is ready, so callers can tell "not ready yet" apart from `None`
+/// ("no more frames").
+#[pyclass]
+pub struct NotReady;
+
+/// Packs `positions`/`colors` into contiguous `(N, 3)` buffers up front
+/// instead of allocating a `PyObject` per coordinate, so the Python side
+/// gets plain `numpy.ndarray`s (`int32` positions, `uint8` colors) via the
+/// buffer protocol rather than a list of per-point tuples.
+fn points_to_pyarray<T, P, F>(py: Python<'_>, points: &[P], extract: F) -> Py<PyArray2<T>>
+where
+    T: numpy::Element,
+    F: Fn(&P) -> [T; 3],
+{
+    let mut flat = Vec::with_capacity(points.len() * 3);
+    for point in points {
+        flat.extend(extract(point));
+    }
+    Array2::from_shape_vec((points.len(), 3), flat)
+        .expect("flat buffer is always a multiple of 3 long")
+        .into_pyarray(py)
+        .into()
+}
+
+fn frame_to_pydict(py: Python<'_>, frame: &PointSet3) -> PyObject {
+    let dict = PyDict::new(py);
+
+    let positions = points_to_pyarray(py, &frame.positions, |p| {
+        [p.x as i32, p.y as i32, p.z as i32]
+    });
+    dict.set_item("positions", positions).ok();
+
+    if frame.with_colors {
+        let colors = points_to_pyarray(py, &frame.colors, |c| [c.x as u8, c.y as u8, c.z as u8]);
+        dict.set_item("colors", colors).ok();
+    }
+
+    dict.into()
+}
+
 #[pyclass]
 pub struct PyTMC2Decoder {
     decoder: Option<Decoder>,
@@ -193,10 +552,16 @@ pub struct PyTMC2Decoder {
 #[pymethods]
 impl PyTMC2Decoder {
     #[new]
-    fn new(_py: Python<'_>, stream: &PyBytes) -> PyResult<Self> {
+    #[pyo3(signature = (stream, frame_buffer_depth=None))]
+    fn new(_py: Python<'_>, stream: &PyBytes, frame_buffer_depth: Option<usize>) -> PyResult<Self> {
         let stream_data = stream.as_bytes().to_vec();
 
-        let mut decoder = Decoder::from_memory(stream_data);
+        let params = Params {
+            source: BitstreamSource::Memory(stream_data),
+            frame_buffer_depth,
+            ..Default::default()
+        };
+        let mut decoder = Decoder::new(params);
 
         decoder.start();
 
@@ -208,27 +573,7 @@ impl PyTMC2Decoder {
     fn next_frame(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
         if let Some(decoder) = &self.decoder {
             match decoder.recv_frame() {
-                Some(frame) => {
-                    let dict = PyDict::new(py);
-
-                    let py_positions = PyList::empty(py);
-                    for pos in frame.positions.iter() {
-                        let tup = PyTuple::new(py, &[pos.x.into_py(py), pos.y.into_py(py), pos.z.into_py(py)]);
-                        py_positions.append(tup).unwrap();
-                    }
-                    dict.set_item("positions", py_positions).ok();
-
-                    if frame.with_colors {
-                        let py_colors = PyList::empty(py);
-                        for col in frame.colors.iter() {
-                            let tup = PyTuple::new(py, &[col.x.into_py(py), col.y.into_py(py), col.z.into_py(py)]);
-                            py_colors.append(tup).unwrap();
-                        }
-                        dict.set_item("colors", py_colors).ok();
-                    }
-
-                    Ok(Some(dict.into()))
-                }
+                Some(frame) => Ok(Some(frame_to_pydict(py, &frame))),
                 None => Ok(None),
             }
         } else {
@@ -236,6 +581,23 @@ impl PyTMC2Decoder {
         }
     }
 
+    /// Non-blocking counterpart to `next_frame()`. Returns the decoded frame
+    /// dict if one is ready, `NotReady` if the decode thread hasn't produced
+    /// one yet, or `None` once decoding has finished for good. This lets
+    /// Python callers poll from e.g. an `asyncio` event loop instead of
+    /// blocking the GIL on a full frame decode.
+    fn try_next_frame(&mut self, py: Python<'_>) -> PyResult<PyObject> {
+        if let Some(decoder) = &self.decoder {
+            match decoder.try_recv_frame() {
+                TryRecvState::Frame(frame) => Ok(frame_to_pydict(py, &frame)),
+                TryRecvState::NotReady => Ok(NotReady.into_py(py)),
+                TryRecvState::Done => Ok(py.None()),
+            }
+        } else {
+            Ok(py.None())
+        }
+    }
+
     fn close(&mut self) {
         self.decoder = None;
     }
@@ -244,5 +606,6 @@ impl PyTMC2Decoder {
 #[pymodule]
 fn tmc2rs(_py: pyo3::Python, m: &pyo3::prelude::PyModule) -> pyo3::PyResult<()> {
     m.add_class::<PyTMC2Decoder>()?;
+    m.add_class::<NotReady>()?;
     Ok(())
 }
\ No newline at end of file
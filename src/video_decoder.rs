@@ -0,0 +1,239 @@
+//! Pluggable backends for decoding the HEVC/AVC video substreams V-PCC
+//! embeds for geometry, occupancy, and attribute data.
+//!
+//! `Params::video_decoder_path` used to imply shelling out to an external
+//! decoder binary. `VideoDecoderBackend` replaces that hardcoded call site
+//! with a trait that `decoder::Decoder` calls instead, selected through
+//! `VideoDecoderRegistry` by name (`Params::video_decoder_backend`), so a
+//! hardware backend can be added later without changing any call sites.
+//! Geometry and attribute substreams may use different bit depths and
+//! profiles, so a backend always advertises what it actually supports and
+//! the decoder falls back to software otherwise.
+//!
+//! NOTE (28Jul26): a VA-API-backed `VideoDecoderBackend` was attempted here
+//! and reverted — it called invented methods on the `cros-libva` crate that
+//! don't exist in the real API, `cros-libva` was never added to
+//! `Cargo.toml`, and the module wasn't feature-gated, so the crate didn't
+//! build.
+//!
+//! The original request asked for both a software default and a
+//! VAAPI-backed implementation; only the former ships here. This crate's
+//! tree has no `Cargo.toml` at all (see the repo root), so there is no
+//! manifest to add `cros-libva` to and no way to gate a `vaapi` feature —
+//! writing the backend without that would mean either re-guessing the
+//! dependency's API from memory again (how the reverted attempt broke) or
+//! landing code that still can't be built or feature-gated either way.
+//! Treating the hardware half as descoped until a manifest exists, rather
+//! than merging another unverifiable implementation, is the honest call
+//! here; re-add it as `vaapi_video_decoder.rs` gated behind
+//! `#[cfg(feature = "vaapi")]`, written against the actual `cros-libva`
+//! surface (`Display::open_display`, `Context::new`, `Surface`/`Picture`
+//! submit+sync, `Image`/`MappedSurface` for the CPU copy-back), once
+//! `Cargo.toml` exists and that dependency can actually be declared.
+
+use std::fmt;
+
+/// Codecs V-PCC may carry in its embedded video substreams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoCodec {
+    Hevc,
+    Avc,
+}
+
+/// A single decoded video substream frame, handed back to the
+/// reconstruction stage as a planar CPU buffer.
+#[derive(Debug, Clone)]
+pub struct DecodedFrame {
+    pub width: usize,
+    pub height: usize,
+    pub bit_depth: u8,
+    /// Planar pixel data in the surface's native layout (e.g. NV12/P010),
+    /// already copied off any hardware surface.
+    pub data: Vec<u8>,
+}
+
+/// Error produced by a `VideoDecoderBackend`.
+#[derive(Debug)]
+pub enum VideoDecoderError {
+    UnsupportedCodec(VideoCodec),
+    UnsupportedProfile { codec: VideoCodec, profile_idc: u8 },
+    Backend(String),
+}
+
+impl fmt::Display for VideoDecoderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VideoDecoderError::UnsupportedCodec(codec) => {
+                write!(f, "video decoder backend does not support {:?}", codec)
+            }
+            VideoDecoderError::UnsupportedProfile { codec, profile_idc } => write!(
+                f,
+                "video decoder backend does not support {:?} profile {}",
+                codec, profile_idc
+            ),
+            VideoDecoderError::Backend(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for VideoDecoderError {}
+
+pub type Result<T> = std::result::Result<T, VideoDecoderError>;
+
+/// A backend capable of decoding one of V-PCC's embedded video substreams.
+///
+/// Implementations advertise which codecs/profiles they can accelerate via
+/// `supports()`, so `decoder::Decoder` can fall back to software decoding
+/// when a requested profile isn't accelerated.
+pub trait VideoDecoderBackend: Send {
+    /// Name this backend is registered under on `Params` (see
+    /// `VideoDecoderRegistry`).
+    fn name(&self) -> &str;
+
+    /// Whether this backend can decode the given codec/profile combination.
+    fn supports(&self, codec: VideoCodec, profile_idc: u8) -> bool;
+
+    /// Decodes a complete NAL unit bitstream for one video substream into
+    /// its constituent frames, in decode order.
+    fn decode_bitstream(&mut self, codec: VideoCodec, nalus: &[u8]) -> Result<Vec<DecodedFrame>>;
+}
+
+/// Registry of available `VideoDecoderBackend`s, looked up by name from
+/// `Params::video_decoder_backend`.
+///
+/// Registered in preference order: `pick()` returns the first backend (by
+/// registration order) that supports the requested codec/profile, falling
+/// back to `software` when nothing else matches.
+#[derive(Default)]
+pub struct VideoDecoderRegistry {
+    backends: Vec<Box<dyn VideoDecoderBackend>>,
+}
+
+impl VideoDecoderRegistry {
+    /// A registry with only the software backend registered, driving the
+    /// external decoder binary at `video_decoder_path`.
+    pub fn software_only(
+        video_decoder_path: std::path::PathBuf,
+        width: usize,
+        height: usize,
+        bit_depth: u8,
+    ) -> Self {
+        let mut registry = Self::default();
+        registry.register(Box::new(SoftwareVideoDecoder::new(
+            video_decoder_path,
+            width,
+            height,
+            bit_depth,
+        )));
+        registry
+    }
+
+    pub fn register(&mut self, backend: Box<dyn VideoDecoderBackend>) {
+        self.backends.push(backend);
+    }
+
+    /// Picks the first registered backend that supports `codec`/`profile_idc`,
+    /// or `None` if nothing (not even software) supports it.
+    pub fn pick(&mut self, codec: VideoCodec, profile_idc: u8) -> Option<&mut dyn VideoDecoderBackend> {
+        self.backends
+            .iter_mut()
+            .find(|backend| backend.supports(codec, profile_idc))
+            .map(|backend| backend.as_mut())
+    }
+
+    pub fn by_name(&mut self, name: &str) -> Option<&mut dyn VideoDecoderBackend> {
+        self.backends
+            .iter_mut()
+            .find(|backend| backend.name() == name)
+            .map(|backend| backend.as_mut())
+    }
+}
+
+/// Software fallback backend. Always available; supports every codec this
+/// crate knows about so `VideoDecoderRegistry::pick()` always has something
+/// to hand back.
+///
+/// Implements the same external-binary mechanism `Params::video_decoder_path`
+/// always implied: `nalus` is written to a temp file, the configured decoder
+/// binary is invoked on it, and its raw planar output is read back as a
+/// single `DecodedFrame`. `width`/`height`/`bit_depth` must be supplied up
+/// front since a raw YUV dump doesn't carry them itself; the real substream
+/// dimensions live in the atlas's frame-size info, which `decoder::Decoder`
+/// threads through when it constructs this backend.
+pub struct SoftwareVideoDecoder {
+    video_decoder_path: std::path::PathBuf,
+    width: usize,
+    height: usize,
+    bit_depth: u8,
+}
+
+impl SoftwareVideoDecoder {
+    pub fn new(video_decoder_path: std::path::PathBuf, width: usize, height: usize, bit_depth: u8) -> Self {
+        Self {
+            video_decoder_path,
+            width,
+            height,
+            bit_depth,
+        }
+    }
+}
+
+impl VideoDecoderBackend for SoftwareVideoDecoder {
+    fn name(&self) -> &str {
+        "software"
+    }
+
+    fn supports(&self, _codec: VideoCodec, _profile_idc: u8) -> bool {
+        true
+    }
+
+    fn decode_bitstream(&mut self, codec: VideoCodec, nalus: &[u8]) -> Result<Vec<DecodedFrame>> {
+        if nalus.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut input = tempfile_path("tmc2rs-video-in");
+        let mut output = tempfile_path("tmc2rs-video-out");
+        std::fs::write(&input, nalus)
+            .map_err(|err| VideoDecoderError::Backend(format!("failed to write substream input: {err}")))?;
+
+        let codec_flag = match codec {
+            VideoCodec::Hevc => "hevc",
+            VideoCodec::Avc => "avc",
+        };
+        let status = std::process::Command::new(&self.video_decoder_path)
+            .arg("--codec")
+            .arg(codec_flag)
+            .arg("--input")
+            .arg(&input)
+            .arg("--output")
+            .arg(&output)
+            .status()
+            .map_err(|err| VideoDecoderError::Backend(format!("failed to spawn video decoder: {err}")))?;
+        let _ = std::fs::remove_file(&input);
+
+        if !status.success() {
+            let _ = std::fs::remove_file(&output);
+            return Err(VideoDecoderError::Backend(format!(
+                "video decoder exited with {status}"
+            )));
+        }
+
+        let data = std::fs::read(&output)
+            .map_err(|err| VideoDecoderError::Backend(format!("failed to read decoded output: {err}")))?;
+        let _ = std::fs::remove_file(&output);
+
+        Ok(vec![DecodedFrame {
+            width: self.width,
+            height: self.height,
+            bit_depth: self.bit_depth,
+            data,
+        }])
+    }
+}
+
+fn tempfile_path(prefix: &str) -> std::path::PathBuf {
+    static COUNTER: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    let unique = COUNTER.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    std::env::temp_dir().join(format!("{prefix}-{}-{unique}.bin", std::process::id()))
+}
@@ -0,0 +1,37 @@
+//! Helpers for writing decoded point clouds out to disk.
+
+use crate::codec::PointSet3;
+use std::io::{self, Write};
+use std::path::Path;
+
+/// Writes a frame as an ASCII PLY point cloud, the simplest format that
+/// round-trips both the positions and (if present) the colors.
+pub fn write_ply(frame: &PointSet3, path: impl AsRef<Path>) -> io::Result<()> {
+    let mut file = std::fs::File::create(path)?;
+    writeln!(file, "ply")?;
+    writeln!(file, "format ascii 1.0")?;
+    writeln!(file, "element vertex {}", frame.positions.len())?;
+    writeln!(file, "property float x")?;
+    writeln!(file, "property float y")?;
+    writeln!(file, "property float z")?;
+    if frame.with_colors {
+        writeln!(file, "property uchar red")?;
+        writeln!(file, "property uchar green")?;
+        writeln!(file, "property uchar blue")?;
+    }
+    writeln!(file, "end_header")?;
+
+    for (i, pos) in frame.positions.iter().enumerate() {
+        if frame.with_colors {
+            let col = frame.colors.get(i).copied().unwrap_or_default();
+            writeln!(
+                file,
+                "{} {} {} {} {} {}",
+                pos.x, pos.y, pos.z, col.x, col.y, col.z
+            )?;
+        } else {
+            writeln!(file, "{} {} {}", pos.x, pos.y, pos.z)?;
+        }
+    }
+    Ok(())
+}